@@ -0,0 +1,113 @@
+/*!
+ *This module validates whether a set of observed event times is consistent
+ *with a given intensity model, using the random time-change theorem and a
+ *Kolmogorov-Smirnov test against the unit-rate exponential distribution.
+ */
+use ndarray::array;
+use ndarray::prelude::*;
+
+/// Rescale event times `t_1 < t_2 < ... < t_n` under a constant-rate Poisson model with
+/// intensity `lambda`, producing ξ_k = Λ(t_k) − Λ(t_{k-1}) = lambda·(t_k − t_{k-1}).
+/// Under the random time-change theorem, if the events are truly generated by this model
+/// the returned values are i.i.d. unit-rate exponentials.
+pub fn rescale_poisson(event_times: &Array1<f64>, lambda: f64) -> Array1<f64> {
+    assert!(lambda >= 0.0);
+    let mut previous_t = 0.0;
+    event_times.mapv(|t| {
+        let xi = lambda*(t - previous_t);
+        previous_t = t;
+        xi
+    })
+}
+
+/// Rescale event times under an exponential-kernel Hawkes model
+/// (λ(t) = lambda0 + alpha·Σ_{t_k<t} exp(-beta(t−t_k))), using the closed form of the
+/// compensator Λ between consecutive events:
+/// Λ(t_{k-1}, t_k) = lambda0·Δt + (alpha/beta)·R_{k-1}·(1 − exp(-beta·Δt)),
+/// where Δt = t_k − t_{k-1} and R_{k-1} = Σ_{t_i ≤ t_{k-1}} exp(-beta(t_{k-1}−t_i)) is
+/// updated recursively as R_k = 1 + exp(-beta·Δt)·R_{k-1}, R_0 = 0.
+pub fn rescale_hawkes(event_times: &Array1<f64>, lambda0: f64, alpha: f64, beta: f64) -> Array1<f64> {
+    assert!(lambda0 >= 0.0 && beta > 0.0);
+    let mut previous_t = 0.0;
+    let mut r = 0.0;
+    event_times.mapv(|t| {
+        let dt = t - previous_t;
+        let decay = (-beta*dt).exp();
+        let xi = lambda0*dt + (alpha/beta)*r*(1.0 - decay);
+        r = decay*r + 1.0;
+        previous_t = t;
+        xi
+    })
+}
+
+/// Perform a one-sample Kolmogorov-Smirnov test of `samples` against Exp(1), returning the
+/// KS statistic `D = sup_x |F_n(x) - (1 - e^-x)|` together with its asymptotic p-value
+/// under the Kolmogorov distribution.
+pub fn ks_test_exp1(samples: &Array1<f64>) -> (f64, f64) {
+    let n = samples.len();
+    assert!(n > 0);
+
+    let mut sorted: Vec<f64> = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let d = sorted.iter().enumerate().map(|(i, &x)| {
+        let empirical_cdf = (i + 1) as f64/n as f64;
+        let empirical_cdf_left = i as f64/n as f64;
+        let model_cdf = 1.0 - (-x.max(0.0)).exp();
+        (empirical_cdf - model_cdf).abs().max((model_cdf - empirical_cdf_left).abs())
+    }).fold(0.0, f64::max);
+
+    let p_value = kolmogorov_sf((n as f64).sqrt()*d);
+
+    (d, p_value)
+}
+
+include!("../../shared/kolmogorov.rs");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::prelude::*;
+
+    #[test]
+    fn rescale_hawkes_matches_known_values() {
+        let times = array![1.0, 2.0, 3.5];
+        let xi = rescale_hawkes(&times, 0.0, 1.0, 1.0);
+        let expected = [0.0, 0.6321205588285577, 1.0626642823991137];
+        for (&x, &e) in xi.iter().zip(expected.iter()) {
+            assert!((x - e).abs() < 1e-9, "{} vs {}", x, e);
+        }
+    }
+
+    #[test]
+    fn ks_test_accepts_true_poisson_model() {
+        let lambda = 2.0;
+        let mut rng = thread_rng();
+        let mut t = 0.0;
+        let mut times = Vec::new();
+        for _ in 0..3000 {
+            t += -rng.gen::<f64>().ln()/lambda;
+            times.push(t);
+        }
+        let xi = rescale_poisson(&Array1::from(times), lambda);
+        let (_, p_value) = ks_test_exp1(&xi);
+        assert!(p_value > 0.01, "p-value too low: {}", p_value);
+    }
+
+    #[test]
+    fn ks_test_rejects_wrong_poisson_rate() {
+        let lambda = 2.0;
+        let mut rng = thread_rng();
+        let mut t = 0.0;
+        let mut times = Vec::new();
+        for _ in 0..3000 {
+            t += -rng.gen::<f64>().ln()/lambda;
+            times.push(t);
+        }
+        // Rescale against a rate far from the one the data was actually generated with:
+        // the resulting xi's should no longer look unit-rate-exponential.
+        let xi = rescale_poisson(&Array1::from(times), 4.0*lambda);
+        let (_, p_value) = ks_test_exp1(&xi);
+        assert!(p_value < 1e-6, "p-value too high for mismatched model: {}", p_value);
+    }
+}
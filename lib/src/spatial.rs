@@ -0,0 +1,206 @@
+/*!
+ *This module generalises the temporal point processes to homogeneous
+ *Poisson processes over bounded domains in ℝᵈ, such as rectangles, balls
+ *and polygons.
+ *
+ *There is no `Vec<Event>`-based counterpart of this module: `Event` carries a single
+ *scalar timestamp, which doesn't fit a d-dimensional point, so this module only exists
+ *in the `Array2<f64>`-based tree.
+ */
+use rand::prelude::*;
+use rand_distr::{Poisson, Distribution};
+
+use ndarray::stack;
+use ndarray::array;
+use ndarray::prelude::*;
+
+use rayon::prelude::*;
+
+/// A bounded subset of ℝᵈ that can be sampled from by rejection against its bounding box.
+pub trait Set: Sync {
+    /// Axis-aligned bounding box, as a 2×d array whose first row is the lower corner and
+    /// second row the upper corner.
+    fn bounding_box(&self) -> Array2<f64>;
+
+    /// Whether `point` (a length-d vector) lies within the domain.
+    fn contains(&self, point: &ArrayView1<f64>) -> bool;
+}
+
+/// An axis-aligned hyperrectangle, defined by its lower and upper corners.
+pub struct Rectangle {
+    pub lower: Array1<f64>,
+    pub upper: Array1<f64>,
+}
+
+impl Rectangle {
+    pub fn new(lower: Array1<f64>, upper: Array1<f64>) -> Rectangle {
+        assert_eq!(lower.len(), upper.len());
+        assert!(lower.iter().zip(upper.iter()).all(|(&l, &u)| l <= u));
+        Rectangle { lower, upper }
+    }
+}
+
+impl Set for Rectangle {
+    fn bounding_box(&self) -> Array2<f64> {
+        stack(Axis(0), &[self.lower.view(), self.upper.view()]).unwrap()
+    }
+
+    fn contains(&self, point: &ArrayView1<f64>) -> bool {
+        point.iter().zip(self.lower.iter()).zip(self.upper.iter())
+            .all(|((&p, &l), &u)| p >= l && p <= u)
+    }
+}
+
+/// A hypersphere, defined by its centre and radius.
+pub struct Ball {
+    pub centre: Array1<f64>,
+    pub radius: f64,
+}
+
+impl Ball {
+    pub fn new(centre: Array1<f64>, radius: f64) -> Ball {
+        assert!(radius >= 0.0);
+        Ball { centre, radius }
+    }
+}
+
+impl Set for Ball {
+    fn bounding_box(&self) -> Array2<f64> {
+        let lower = &self.centre - self.radius;
+        let upper = &self.centre + self.radius;
+        stack(Axis(0), &[lower.view(), upper.view()]).unwrap()
+    }
+
+    fn contains(&self, point: &ArrayView1<f64>) -> bool {
+        let diff = point - &self.centre;
+        diff.dot(&diff).sqrt() <= self.radius
+    }
+}
+
+/// A simple polygon in the plane (d = 2), defined by its vertices in order. Containment is
+/// tested with the standard ray-casting algorithm.
+pub struct Polygon {
+    pub vertices: Vec<(f64, f64)>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<(f64, f64)>) -> Polygon {
+        assert!(vertices.len() >= 3);
+        Polygon { vertices }
+    }
+}
+
+impl Set for Polygon {
+    fn bounding_box(&self) -> Array2<f64> {
+        let xs: Vec<f64> = self.vertices.iter().map(|&(x, _)| x).collect();
+        let ys: Vec<f64> = self.vertices.iter().map(|&(_, y)| y).collect();
+        let lower = array![xs.iter().cloned().fold(f64::INFINITY, f64::min),
+                           ys.iter().cloned().fold(f64::INFINITY, f64::min)];
+        let upper = array![xs.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+                           ys.iter().cloned().fold(f64::NEG_INFINITY, f64::max)];
+        stack(Axis(0), &[lower.view(), upper.view()]).unwrap()
+    }
+
+    fn contains(&self, point: &ArrayView1<f64>) -> bool {
+        assert_eq!(point.len(), 2);
+        let (x, y) = (point[0], point[1]);
+        let n = self.vertices.len();
+        let mut inside = false;
+        let mut j = n - 1;
+        for i in 0..n {
+            let (xi, yi) = self.vertices[i];
+            let (xj, yj) = self.vertices[j];
+            if (yi > y) != (yj > y) && x < (xj - xi)*(y - yi)/(yj - yi) + xi {
+                inside = !inside;
+            }
+            j = i;
+        }
+        inside
+    }
+}
+
+/// Simulate a homogeneous Poisson process of intensity `lambda` over an arbitrary bounded
+/// `domain`, generalising `temporal::poisson_process` to d dimensions.
+/// Draws `N ~ Poisson(lambda·volume)` points uniformly in the domain's bounding box in
+/// parallel via rayon, then keeps only those that actually lie within the domain.
+/// Returns an n×d array of accepted points.
+pub fn poisson_process<T: Set>(lambda: f64, domain: &T) -> Array2<f64> {
+    assert!(lambda >= 0.0);
+
+    let bbox = domain.bounding_box();
+    let lower = bbox.row(0);
+    let upper = bbox.row(1);
+    let d = lower.len();
+    let volume: f64 = lower.iter().zip(upper.iter()).map(|(&l, &u)| u - l).product();
+
+    let mut rng = thread_rng();
+    let fish = Poisson::new(lambda*volume).unwrap();
+    let num_points: u64 = fish.sample(&mut rng);
+
+    let points: Vec<Array2<f64>> = (0..num_points).into_par_iter().filter_map(|_| {
+        let mut rng = thread_rng();
+        let point: Array1<f64> = Array1::from_shape_fn(d, |i| lower[i] + rng.gen::<f64>()*(upper[i] - lower[i]));
+        if domain.contains(&point.view()) {
+            Some(point.insert_axis(Axis(0)))
+        } else {
+            None
+        }
+    }).collect();
+
+    if points.len() > 0 {
+        let points_ref: Vec<ArrayView2<f64>> = points.iter().map(|v| v.view()).collect();
+        stack(Axis(0), points_ref.as_slice()).unwrap()
+    } else {
+        Array2::<f64>::zeros((0, d))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ball_contains_points_inside_and_excludes_points_outside() {
+        let ball = Ball::new(array![1.0, 1.0], 2.0);
+        assert!(ball.contains(&array![1.0, 1.0].view())); // centre
+        assert!(ball.contains(&array![2.0, 1.0].view())); // distance 1, inside
+        assert!(ball.contains(&array![3.0, 1.0].view())); // distance 2, on boundary
+        assert!(!ball.contains(&array![4.0, 1.0].view())); // distance 3, outside
+    }
+
+    #[test]
+    fn polygon_contains_known_inside_outside_and_near_edge_points() {
+        // Unit square [0,1] x [0,1].
+        let square = Polygon::new(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]);
+        assert!(square.contains(&array![0.5, 0.5].view())); // centre, clearly inside
+        assert!(!square.contains(&array![1.5, 0.5].view())); // clearly outside
+        assert!(!square.contains(&array![-0.5, 0.5].view())); // clearly outside
+        assert!(square.contains(&array![0.999, 0.5].view())); // just inside the right edge
+        assert!(!square.contains(&array![1.001, 0.5].view())); // just outside the right edge
+    }
+
+    #[test]
+    fn poisson_process_points_lie_in_domain_and_count_scales_with_volume() {
+        let small = Rectangle::new(array![0.0, 0.0], array![1.0, 1.0]);
+        let large = Rectangle::new(array![0.0, 0.0], array![2.0, 2.0]);
+        let lambda = 10.0;
+
+        let small_points = poisson_process(lambda, &small);
+        for point in small_points.outer_iter() {
+            assert!(small.contains(&point));
+        }
+
+        let large_points = poisson_process(lambda, &large);
+        for point in large_points.outer_iter() {
+            assert!(large.contains(&point));
+        }
+
+        // Expected counts are lambda·volume = 10 and 40; allow a generous margin since
+        // this is a single stochastic draw.
+        assert!((small_points.nrows() as f64 - 10.0).abs() < 20.0,
+                "{} points in unit square", small_points.nrows());
+        assert!((large_points.nrows() as f64 - 40.0).abs() < 40.0,
+                "{} points in 2x2 square", large_points.nrows());
+        assert!(large_points.nrows() > small_points.nrows());
+    }
+}
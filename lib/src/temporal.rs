@@ -5,7 +5,7 @@
  */
 use rand::prelude::*;
 use rand::distributions::Uniform;
-use rand_distr::{Poisson, Distribution};
+use rand_distr::{Poisson, Normal, Gamma, Distribution};
 
 use ndarray::stack;
 use ndarray::array;
@@ -65,6 +65,70 @@ where F: Fn(f64) -> f64 + Send + Sync
     }
 }
 
+/// Simulate a Poisson process with variable intensity via the time-rescaling theorem:
+/// invert the cumulative intensity Λ(t) = ∫₀ᵗ λ(s) ds at unit-rate exponential arrival
+/// levels, using a trapezoidal grid of `n_grid` cells with binary search and linear
+/// interpolation. Unlike `variable_poisson`, this needs no `max_lambda` bound.
+/// index 0: timestamps, index 1: intensity
+pub fn variable_poisson_inverted<F>(tmax: f64, lambda: &F, n_grid: usize) -> Array2<f64>
+where F: Fn(f64) -> f64 + Sync
+{
+    assert!(tmax > 0.0);
+    assert!(n_grid > 0);
+    let dt = tmax/n_grid as f64;
+
+    // Trapezoidal cumulative intensity Λ on the grid 0, dt, 2dt, ..., tmax.
+    let mut grid_times = Vec::with_capacity(n_grid + 1);
+    let mut grid_lambda = Vec::with_capacity(n_grid + 1);
+    let mut grid_cumulative = Vec::with_capacity(n_grid + 1);
+    let mut cumulative = 0.0;
+    let mut previous_lambda = lambda(0.0);
+    grid_times.push(0.0);
+    grid_lambda.push(previous_lambda);
+    grid_cumulative.push(0.0);
+    for i in 1..=n_grid {
+        let t = i as f64*dt;
+        let lambda_t = lambda(t);
+        cumulative += 0.5*(previous_lambda + lambda_t)*dt;
+        grid_times.push(t);
+        grid_lambda.push(lambda_t);
+        grid_cumulative.push(cumulative);
+        previous_lambda = lambda_t;
+    }
+
+    let mut rng = thread_rng();
+    let mut result = Vec::<Array2<f64>>::new();
+    let mut s = 0.0;
+    loop {
+        s += -rng.gen::<f64>().ln();
+        if s > cumulative {
+            break;
+        }
+
+        // Bisect the cumulative grid for s, then linearly interpolate
+        // both the event time and the intensity at that time.
+        let idx = match grid_cumulative.binary_search_by(|v| v.partial_cmp(&s).unwrap()) {
+            Ok(i) => i,
+            Err(i) => i.min(n_grid),
+        };
+        let (lo, hi) = if idx == 0 { (0, 1) } else { (idx - 1, idx.min(n_grid)) };
+        let frac = if grid_cumulative[hi] > grid_cumulative[lo] {
+            (s - grid_cumulative[lo])/(grid_cumulative[hi] - grid_cumulative[lo])
+        } else { 0.0 };
+        let t = grid_times[lo] + frac*(grid_times[hi] - grid_times[lo]);
+        let lambda_t = grid_lambda[lo] + frac*(grid_lambda[hi] - grid_lambda[lo]);
+
+        result.push(array![[t, lambda_t]]);
+    }
+
+    if result.len() > 0 {
+        let events: Vec<ArrayView2<f64>> = result.iter().map(|v| v.view()).collect();
+        stack(Axis(0), &events).unwrap()
+    } else {
+        Array2::<f64>::zeros((0,2))
+    }
+}
+
 /// Simulate a time-dependent marked Hawkes process with an exponential kernel.
 /// index 0: timestamps, index 1: intensity, index 2: marks
 pub fn hawkes_exponential(tmax: f64, decay: f64, lambda0: f64, alpha: f64) -> Array2<f64>
@@ -108,4 +172,330 @@ pub fn hawkes_exponential(tmax: f64, decay: f64, lambda0: f64, alpha: f64) -> Ar
     } else {
         Array2::<f64>::zeros((0,3))
     }
+}
+
+/// Simulate a multivariate, mutually-exciting Hawkes process over `D` components via
+/// Ogata's thinning, with a per-(target, source) exponential kernel `alpha[i][j]`/
+/// `beta[i][j]`: λᵢ(t) = μᵢ + Σⱼ Σ_{tₖ in component j, tₖ<t} alphaᵢⱼ·exp(-betaᵢⱼ(t-tₖ)).
+/// index 0: timestamps, index 1: component
+pub fn hawkes_multivariate(tmax: f64, baselines: &Array1<f64>, alpha: &Array2<f64>, beta: &Array2<f64>) -> Array2<f64>
+{
+    let d = baselines.len();
+    assert_eq!(alpha.shape(), &[d, d]);
+    assert_eq!(beta.shape(), &[d, d]);
+
+    let mut rng = thread_rng();
+    let mut t = 0.0;
+    // contrib[[i, j]]: decaying excitation contributed to component i by
+    // past events in component j.
+    let mut contrib = Array2::<f64>::zeros((d, d));
+    let mut result = Vec::<Array2<f64>>::new();
+
+    while t < tmax {
+        let lambda: Array1<f64> = baselines + &contrib.sum_axis(Axis(1));
+        let lambda_bar: f64 = lambda.sum();
+        if lambda_bar <= 0.0 {
+            break;
+        }
+
+        let dt = -1.0/lambda_bar*rng.gen::<f64>().ln();
+        let candidate_t = t + dt;
+        if candidate_t > tmax {
+            break;
+        }
+
+        // Decay every source/target excitation pair to the candidate time.
+        contrib = &contrib * &beta.mapv(|b| (-b*dt).exp());
+        let decayed_lambda: Array1<f64> = baselines + &contrib.sum_axis(Axis(1));
+        let decayed_bar: f64 = decayed_lambda.sum();
+
+        t = candidate_t;
+        if rng.gen::<f64>() < decayed_bar/lambda_bar {
+            // Accept the candidate, assigning it to component i with
+            // probability proportional to that component's intensity.
+            let u = rng.gen::<f64>()*decayed_bar;
+            let mut cumulative = 0.0;
+            let mut component = d - 1;
+            for i in 0..d {
+                cumulative += decayed_lambda[i];
+                if u < cumulative {
+                    component = i;
+                    break;
+                }
+            }
+            result.push(array![[t, component as f64]]);
+            for i in 0..d {
+                contrib[[i, component]] += alpha[[i, component]];
+            }
+        }
+    }
+
+    if result.len() > 0 {
+        let events: Vec<ArrayView2<f64>> = result.iter().map(|v| v.view()).collect();
+        stack(Axis(0), &events).unwrap()
+    } else {
+        Array2::<f64>::zeros((0,2))
+    }
+}
+
+#[cfg(test)]
+mod hawkes_multivariate_tests {
+    use super::*;
+
+    #[test]
+    fn symmetric_components_split_events_evenly() {
+        let baselines = array![1.0, 1.0];
+        // Self-excitation only, equal on both components: no coupling between them.
+        let alpha = array![[0.5, 0.0], [0.0, 0.5]];
+        let beta = array![[2.0, 2.0], [2.0, 2.0]];
+        let tmax = 2000.0;
+
+        let events = hawkes_multivariate(tmax, &baselines, &alpha, &beta);
+        assert!(events.nrows() > 0);
+
+        let mut previous_t = f64::NEG_INFINITY;
+        let mut component_0 = 0;
+        for row in events.outer_iter() {
+            let (t, component) = (row[0], row[1]);
+            assert!(t > previous_t, "timestamps must be strictly increasing");
+            assert!(t <= tmax);
+            previous_t = t;
+            if component == 0.0 {
+                component_0 += 1;
+            }
+        }
+
+        let fraction_0 = component_0 as f64/events.nrows() as f64;
+        assert!((fraction_0 - 0.5).abs() < 0.05, "component split {} not close to 1/2", fraction_0);
+    }
+}
+
+/// Simulate a Cox (doubly-stochastic) Poisson process: the intensity follows an
+/// Euler-Maruyama-simulated Ornstein-Uhlenbeck path (clamped at zero), and events are
+/// thinned against its running maximum. Returns the events alongside the full
+/// `(time, intensity)` trajectory of the latent driver.
+/// index 0: timestamps, index 1: intensity
+pub fn cox_process(tmax: f64, theta: f64, mu: f64, lambda0: f64, sigma: f64, dt: f64) -> (Array2<f64>, Array2<f64>)
+{
+    assert!(dt > 0.0);
+    assert!(lambda0 >= 0.0);
+
+    let mut rng = thread_rng();
+    let normal = Normal::new(0.0, 1.0).unwrap();
+
+    let n_steps = (tmax/dt).ceil() as usize;
+    let mut path = Vec::with_capacity(n_steps + 1);
+    path.push((0.0, lambda0));
+
+    let mut lambda = lambda0;
+    for n in 1..=n_steps {
+        let t = (n as f64*dt).min(tmax);
+        let z: f64 = normal.sample(&mut rng);
+        lambda = (lambda + theta*(mu - lambda)*dt + sigma*dt.sqrt()*z).max(0.0);
+        path.push((t, lambda));
+    }
+
+    // Running maximum of the simulated path is used as the thinning bound.
+    let lambda_bar = path.iter().map(|&(_, l)| l).fold(0.0, f64::max);
+
+    let mut result = Vec::<Array2<f64>>::new();
+    if lambda_bar > 0.0 {
+        let mut t = 0.0;
+        loop {
+            t += -1.0/lambda_bar*rng.gen::<f64>().ln();
+            if t > tmax {
+                break;
+            }
+            let lambda_t = interpolate_path(&path, t);
+            if rng.gen::<f64>()*lambda_bar < lambda_t {
+                result.push(array![[t, lambda_t]]);
+            }
+        }
+    }
+
+    let events = if result.len() > 0 {
+        let events_ref: Vec<ArrayView2<f64>> = result.iter().map(|v| v.view()).collect();
+        stack(Axis(0), events_ref.as_slice()).unwrap()
+    } else {
+        Array2::<f64>::zeros((0,2))
+    };
+
+    let trajectory = Array2::from_shape_vec((path.len(), 2), path.into_iter().flat_map(|(t, l)| vec![t, l]).collect()).unwrap();
+
+    (events, trajectory)
+}
+
+#[cfg(test)]
+mod cox_process_tests {
+    use super::*;
+
+    #[test]
+    fn event_rate_matches_path_average_intensity() {
+        let tmax = 200.0;
+        let (events, trajectory) = cox_process(tmax, 1.0, 3.0, 3.0, 0.5, 0.05);
+
+        for row in trajectory.outer_iter() {
+            assert!(row[1] >= 0.0, "OU path should be clamped at zero, got {}", row[1]);
+        }
+
+        let average_intensity = trajectory.column(1).sum()/trajectory.nrows() as f64;
+        let expected_events = average_intensity*tmax;
+        let count = events.nrows() as f64;
+        assert!((count - expected_events).abs() < 4.0*expected_events.sqrt(),
+                "{} events vs expected {}", count, expected_events);
+    }
+}
+
+/// Linearly interpolate a `(time, value)` path, assumed sorted by time, at
+/// an arbitrary query time. Clamps to the first/last value outside the
+/// path's range.
+fn interpolate_path(path: &[(f64, f64)], t: f64) -> f64 {
+    match path.binary_search_by(|&(pt, _)| pt.partial_cmp(&t).unwrap()) {
+        Ok(i) => path[i].1,
+        Err(i) => {
+            if i == 0 {
+                path[0].1
+            } else if i >= path.len() {
+                path[path.len() - 1].1
+            } else {
+                let (t0, l0) = path[i - 1];
+                let (t1, l1) = path[i];
+                let frac = (t - t0)/(t1 - t0);
+                l0 + frac*(l1 - l0)
+            }
+        }
+    }
+}
+
+/// Simulate a renewal process whose inter-arrival gaps are drawn from an arbitrary
+/// `sampler` (e.g. gamma, Weibull, log-normal), generalising the Poisson process's
+/// memoryless exponential gaps. The intensity column is set to the reciprocal of each
+/// event's own gap, as a rough instantaneous-rate summary.
+/// index 0: timestamps, index 1: intensity
+pub fn renewal_process<F>(tmax: f64, mut sampler: F) -> Array2<f64>
+where F: FnMut() -> f64
+{
+    let mut t = 0.0;
+    let mut result = Vec::<Array2<f64>>::new();
+
+    while t < tmax {
+        let gap = sampler();
+        assert!(gap >= 0.0);
+        t += gap;
+        if t > tmax {
+            break;
+        }
+        result.push(array![[t, 1.0/gap]]);
+    }
+
+    if result.len() > 0 {
+        let events: Vec<ArrayView2<f64>> = result.iter().map(|v| v.view()).collect();
+        stack(Axis(0), &events).unwrap()
+    } else {
+        Array2::<f64>::zeros((0,2))
+    }
+}
+
+#[cfg(test)]
+mod variable_poisson_inverted_tests {
+    use super::*;
+
+    #[test]
+    fn event_count_and_intensity_match_closed_form_cumulative_intensity() {
+        let (a, b) = (1.0, 0.5);
+        let lambda = |t: f64| a + b*t;
+        let tmax = 10.0;
+        // Λ(tmax) = a·tmax + b·tmax²/2 = 35, with std √35 ≈ 5.9 for the event count.
+        let expected_count = a*tmax + b*tmax*tmax/2.0;
+
+        let events = variable_poisson_inverted(tmax, &lambda, 2000);
+        let count = events.nrows() as f64;
+        assert!((count - expected_count).abs() < 4.0*expected_count.sqrt(),
+                "{} events vs expected {}", count, expected_count);
+
+        for row in events.outer_iter() {
+            let (t, intensity) = (row[0], row[1]);
+            assert!(t >= 0.0 && t <= tmax);
+            assert!((intensity - lambda(t)).abs() < 1e-2, "{} vs {}", intensity, lambda(t));
+        }
+    }
+}
+
+/// Simulate a renewal process from an age-dependent hazard `h(age)`: advancing in steps
+/// of `dt`, firing with probability `h(age)*dt` and resetting the age on firing. Models
+/// refractory/bursty streams that a memoryless Poisson process cannot capture.
+/// index 0: timestamps, index 1: intensity (the hazard at the firing age)
+pub fn hazard_renewal_process<H>(tmax: f64, hazard: H, dt: f64) -> Array2<f64>
+where H: Fn(f64) -> f64
+{
+    assert!(dt > 0.0);
+    let mut rng = thread_rng();
+    let mut t = 0.0;
+    let mut age = 0.0;
+    let mut result = Vec::<Array2<f64>>::new();
+
+    while t < tmax {
+        let h = hazard(age);
+        if rng.gen::<f64>() < h*dt {
+            result.push(array![[t, h]]);
+            age = 0.0;
+        } else {
+            age += dt;
+        }
+        t += dt;
+    }
+
+    if result.len() > 0 {
+        let events: Vec<ArrayView2<f64>> = result.iter().map(|v| v.view()).collect();
+        stack(Axis(0), &events).unwrap()
+    } else {
+        Array2::<f64>::zeros((0,2))
+    }
+}
+
+#[cfg(test)]
+mod renewal_process_tests {
+    use super::*;
+
+    fn assert_increasing_and_bounded(events: &Array2<f64>, tmax: f64) {
+        let mut previous_t = f64::NEG_INFINITY;
+        for row in events.outer_iter() {
+            let t = row[0];
+            assert!(t > previous_t, "timestamps must be strictly increasing");
+            assert!(t <= tmax);
+            previous_t = t;
+        }
+    }
+
+    #[test]
+    fn renewal_process_timestamps_are_increasing_and_bounded() {
+        let tmax = 500.0;
+        let rate = 2.0;
+        let mut rng = thread_rng();
+        let gamma = Gamma::new(2.0, 1.0/rate).unwrap();
+        let events = renewal_process(tmax, || gamma.sample(&mut rng));
+        assert!(events.nrows() > 0);
+        assert_increasing_and_bounded(&events, tmax);
+    }
+
+    #[test]
+    fn hazard_renewal_process_timestamps_are_increasing_and_bounded() {
+        let tmax = 500.0;
+        let events = hazard_renewal_process(tmax, |_age| 0.1, 0.01);
+        assert!(events.nrows() > 0);
+        assert_increasing_and_bounded(&events, tmax);
+    }
+
+    #[test]
+    fn hazard_renewal_process_with_constant_hazard_matches_poisson_rate() {
+        // A constant hazard h(age) = lambda is memoryless, so the firing rate should
+        // match a homogeneous Poisson process of the same intensity.
+        let tmax = 2000.0;
+        let lambda = 0.5;
+        let events = hazard_renewal_process(tmax, |_age| lambda, 0.01);
+        let expected_count = lambda*tmax;
+        let count = events.nrows() as f64;
+        assert!((count - expected_count).abs() < 4.0*expected_count.sqrt(),
+                "{} events vs expected {}", count, expected_count);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,67 @@
+// Shared between src/estimation.rs and lib/src/estimation.rs via `include!`, since the
+// two crates can't depend on each other without a shared library target. See
+// `shared/README.md` for the rationale.
+
+/// Natural log of the Gamma function via the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const G: f64 = 7.0;
+    const COEFFICIENTS: [f64; 9] = [
+        0.99999999999980993, 676.5203681218851, -1259.1392167224028,
+        771.32342877765313, -176.61502916214059, 12.507343278686905,
+        -0.13857109526572012, 9.9843695780195716e-6, 1.5056327351493116e-7,
+    ];
+    if x < 0.5 {
+        return (std::f64::consts::PI/(std::f64::consts::PI*x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + G + 0.5;
+    let mut a = COEFFICIENTS[0];
+    for (i, &c) in COEFFICIENTS.iter().enumerate().skip(1) {
+        a += c/(x + i as f64);
+    }
+    0.5*(2.0*std::f64::consts::PI).ln() + (x + 0.5)*t.ln() - t + a.ln()
+}
+
+/// Regularised lower incomplete gamma function P(shape, x) = γ(shape,x)/Γ(shape), i.e. the
+/// CDF of a Gamma(shape, rate=1) distribution at x, via series expansion (x < shape+1) or a
+/// continued fraction (x >= shape+1), as in Numerical Recipes.
+fn regularized_gamma_p(shape: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x < shape + 1.0 {
+        let mut term = 1.0/shape;
+        let mut sum = term;
+        let mut n = shape;
+        for _ in 0..200 {
+            n += 1.0;
+            term *= x/n;
+            sum += term;
+            if term.abs() < sum.abs()*1e-14 {
+                break;
+            }
+        }
+        sum*(-x + shape*x.ln() - ln_gamma(shape)).exp()
+    } else {
+        let mut b = x + 1.0 - shape;
+        let mut c = 1.0/1e-300;
+        let mut d = 1.0/b;
+        let mut h = d;
+        for i in 1..200 {
+            let an = -(i as f64)*(i as f64 - shape);
+            b += 2.0;
+            d = an*d + b;
+            if d.abs() < 1e-300 { d = 1e-300; }
+            c = b + an/c;
+            if c.abs() < 1e-300 { c = 1e-300; }
+            d = 1.0/d;
+            let delta = d*c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-14 {
+                break;
+            }
+        }
+        let q = (-x + shape*x.ln() - ln_gamma(shape)).exp()*h;
+        1.0 - q
+    }
+}
@@ -0,0 +1,20 @@
+// Shared between src/analysis.rs and lib/src/analysis.rs via `include!`, since the two
+// crates can't depend on each other without a shared library target. See
+// `shared/README.md` for the rationale.
+
+/// Asymptotic survival function of the Kolmogorov distribution,
+/// Q(x) = 2·Σ_{k=1}^∞ (-1)^(k-1)·exp(-2k²x²), truncated once terms become negligible.
+fn kolmogorov_sf(x: f64) -> f64 {
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let mut total = 0.0;
+    for k in 1..=100 {
+        let term = (-2.0*(k as f64).powi(2)*x*x).exp();
+        total += if k % 2 == 1 { term } else { -term };
+        if term < 1e-12 {
+            break;
+        }
+    }
+    (2.0*total).max(0.0).min(1.0)
+}
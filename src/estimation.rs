@@ -0,0 +1,202 @@
+/*!
+This module recovers process parameters from observed event data, via maximum likelihood
+and, for the homogeneous Poisson process, a conjugate Bayesian posterior.
+*/
+
+use rand::prelude::*;
+use rand::distributions::Gamma;
+
+/// Maximum-likelihood intensity estimate for a homogeneous Poisson process observed on
+/// `[0, tmax]`: λ̂ = N/tmax.
+pub fn poisson_mle(event_times: &[f64], tmax: f64) -> f64 {
+    assert!(tmax > 0.0);
+    event_times.len() as f64/tmax
+}
+
+/// Posterior over the intensity of a homogeneous Poisson process under a Gamma(a, b)
+/// prior, after observing `N` events over `[0, tmax]`: the conjugate posterior is
+/// Gamma(a + N, b + tmax), parametrised here by shape and rate.
+pub struct PoissonPosterior {
+    pub shape: f64,
+    pub rate: f64,
+}
+
+impl PoissonPosterior {
+    /// Compute the Gamma(a + N, b + tmax) posterior given a Gamma(a, b) prior.
+    pub fn new(event_times: &[f64], tmax: f64, prior_shape: f64, prior_rate: f64) -> PoissonPosterior {
+        assert!(tmax > 0.0 && prior_shape > 0.0 && prior_rate > 0.0);
+        PoissonPosterior {
+            shape: prior_shape + event_times.len() as f64,
+            rate: prior_rate + tmax,
+        }
+    }
+
+    /// Posterior mean, shape/rate.
+    pub fn mean(&self) -> f64 {
+        self.shape/self.rate
+    }
+
+    /// Equal-tailed credible interval at the given confidence level (e.g. 0.95), found by
+    /// bisecting the posterior's CDF.
+    pub fn credible_interval(&self, confidence: f64) -> (f64, f64) {
+        assert!(confidence > 0.0 && confidence < 1.0);
+        let tail = (1.0 - confidence)/2.0;
+        (self.quantile(tail), self.quantile(1.0 - tail))
+    }
+
+    /// Quantile function of the posterior, found by bisection on the regularised
+    /// incomplete gamma function.
+    fn quantile(&self, p: f64) -> f64 {
+        let mut lo = 0.0;
+        let mut hi = (self.shape + 10.0*self.shape.sqrt())/self.rate + 1.0;
+        for _ in 0..200 {
+            let mid = 0.5*(lo + hi);
+            if regularized_gamma_p(self.shape, self.rate*mid) < p {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        0.5*(lo + hi)
+    }
+
+    /// Draw a sample from the posterior.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> f64 {
+        Gamma::new(self.shape, 1.0/self.rate).sample(rng)
+    }
+}
+
+include!("../shared/gamma.rs");
+
+/// Exact log-likelihood of an exponential-kernel Hawkes process
+/// (λ(t) = lambda0 + alpha·Σ_{t_k<t} exp(-beta(t−t_k))) given `event_times` observed on
+/// `[0, tmax]`:
+/// ℓ = Σ_k log λ(t_k) − [lambda0·tmax + (alpha/beta)·Σ_k(1 − e^{-beta(tmax−t_k)})],
+/// where λ(t_k) is evaluated via the recursive sum R_k = 1 + e^{-beta(t_k−t_{k-1})}·R_{k-1}.
+pub fn hawkes_log_likelihood(event_times: &[f64], tmax: f64, lambda0: f64, alpha: f64, beta: f64) -> f64 {
+    assert!(lambda0 > 0.0 && beta > 0.0);
+
+    let mut previous_t = 0.0;
+    let mut r = 0.0;
+    let mut log_intensity_sum = 0.0;
+    let mut compensator_sum = 0.0;
+
+    for &t in event_times.iter() {
+        let dt = t - previous_t;
+        r *= (-beta*dt).exp();
+        log_intensity_sum += (lambda0 + alpha*r).ln();
+        r += 1.0;
+        previous_t = t;
+        compensator_sum += 1.0 - (-beta*(tmax - t)).exp();
+    }
+
+    let compensator = lambda0*tmax + (alpha/beta)*compensator_sum;
+    log_intensity_sum - compensator
+}
+
+/// Fit `(lambda0, alpha, beta)` of an exponential-kernel Hawkes process to `event_times` by
+/// maximising `hawkes_log_likelihood` with gradient ascent using a finite-difference
+/// approximation of the gradient, since the likelihood is smooth but has no convenient
+/// closed-form gradient in `beta`.
+pub fn hawkes_mle(event_times: &[f64], tmax: f64, initial: (f64, f64, f64), learning_rate: f64, iterations: usize) -> (f64, f64, f64) {
+    let (mut lambda0, mut alpha, mut beta) = initial;
+    let eps = 1e-5;
+
+    let ll = |lambda0: f64, alpha: f64, beta: f64| {
+        hawkes_log_likelihood(event_times, tmax, lambda0.max(eps), alpha.max(0.0), beta.max(eps))
+    };
+
+    for _ in 0..iterations {
+        let grad_lambda0 = (ll(lambda0 + eps, alpha, beta) - ll(lambda0 - eps, alpha, beta))/(2.0*eps);
+        let grad_alpha = (ll(lambda0, alpha + eps, beta) - ll(lambda0, alpha - eps, beta))/(2.0*eps);
+        let grad_beta = (ll(lambda0, alpha, beta + eps) - ll(lambda0, alpha, beta - eps))/(2.0*eps);
+
+        lambda0 = (lambda0 + learning_rate*grad_lambda0).max(eps);
+        alpha = (alpha + learning_rate*grad_alpha).max(0.0);
+        beta = (beta + learning_rate*grad_beta).max(eps);
+    }
+
+    (lambda0, alpha, beta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn poisson_mle_recovers_true_lambda() {
+        let lambda = 3.0;
+        let tmax = 10_000.0;
+        let mut rng = thread_rng();
+        let mut t = 0.0;
+        let mut times = Vec::new();
+        while t < tmax {
+            t += -rng.gen::<f64>().ln()/lambda;
+            if t < tmax {
+                times.push(t);
+            }
+        }
+        let lambda_hat = poisson_mle(&times, tmax);
+        assert!((lambda_hat - lambda).abs() < 0.1, "{} vs {}", lambda_hat, lambda);
+    }
+
+    #[test]
+    fn poisson_posterior_mean_matches_conjugate_formula() {
+        let times = [0.5, 1.5, 2.5, 3.5];
+        let tmax = 4.0;
+        let posterior = PoissonPosterior::new(&times, tmax, 2.0, 1.0);
+        // Gamma(2, 1) prior + 4 events over [0, 4] -> Gamma(2 + 4, 1 + 4) = Gamma(6, 5)
+        // posterior, with mean 6/5 = 1.2, worked out by hand rather than by re-running
+        // the implementation's own shape/rate formula.
+        assert!((posterior.mean() - 1.2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn hawkes_log_likelihood_reduces_to_poisson_when_alpha_is_zero() {
+        let times = [1.0, 2.0, 3.5];
+        let lambda0 = 1.7;
+        let tmax = 4.0;
+        let ll = hawkes_log_likelihood(&times, tmax, lambda0, 0.0, 1.0);
+        let expected = times.len() as f64*lambda0.ln() - lambda0*tmax;
+        assert!((ll - expected).abs() < 1e-9, "{} vs {}", ll, expected);
+    }
+
+    #[test]
+    fn hawkes_mle_recovers_true_parameters() {
+        let (lambda0, alpha, beta) = (0.4, 0.8, 1.5);
+        let tmax = 20_000.0;
+
+        // Simulate an exponential-kernel Hawkes process by Ogata's thinning (unlike
+        // `timedependent::hawkes_exponential`, which uses Dassios and Zhao's method).
+        let mut rng = thread_rng();
+        let mut times = Vec::new();
+        let mut s = -1.0/lambda0*rng.gen::<f64>().ln();
+        let mut cur_lambda = lambda0 + alpha;
+        let mut lbda_max = cur_lambda;
+        if s < tmax {
+            times.push(s);
+        }
+        while s < tmax {
+            let u: f64 = rng.gen();
+            let ds = -1.0/lbda_max*u.ln();
+            cur_lambda = lambda0 + (cur_lambda - lambda0)*(-beta*ds).exp();
+            s += ds;
+            if s > tmax {
+                break;
+            }
+            let d: f64 = rng.gen();
+            if d < cur_lambda/lbda_max {
+                cur_lambda += alpha;
+                times.push(s);
+            }
+            lbda_max = cur_lambda;
+        }
+
+        let (lambda0_hat, alpha_hat, beta_hat) =
+            hawkes_mle(&times, tmax, (0.2, 0.2, 1.0), 1e-6, 3000);
+
+        assert!((lambda0_hat - lambda0).abs() < 0.1, "{} vs {}", lambda0_hat, lambda0);
+        assert!((alpha_hat - alpha).abs() < 0.2, "{} vs {}", alpha_hat, alpha);
+        assert!((beta_hat - beta).abs() < 0.3, "{} vs {}", beta_hat, beta);
+    }
+}
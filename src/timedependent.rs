@@ -59,6 +59,63 @@ where F: Fn(f64) -> f64 + Send + Sync
     }).collect()
 }
 
+/// Simulate a Poisson process with variable intensity via the time-rescaling theorem:
+/// invert the cumulative intensity Λ(t) = ∫₀ᵗ λ(s) ds at unit-rate exponential arrival
+/// levels, using a trapezoidal grid of `n_grid` cells with binary search and linear
+/// interpolation. Unlike `variable_poisson`, this needs no `max_lambda` bound.
+pub fn variable_poisson_inverted<F>(tmax: f64, lambda: F, n_grid: usize) -> Vec<Event>
+where F: Fn(f64) -> f64 + Sync
+{
+    assert!(tmax > 0.0);
+    assert!(n_grid > 0);
+    let dt = tmax/n_grid as f64;
+
+    // Trapezoidal cumulative intensity Λ on the grid 0, dt, 2dt, ..., tmax.
+    let mut grid_times = Vec::with_capacity(n_grid + 1);
+    let mut grid_lambda = Vec::with_capacity(n_grid + 1);
+    let mut grid_cumulative = Vec::with_capacity(n_grid + 1);
+    let mut cumulative = 0.0;
+    let mut previous_lambda = lambda(0.0);
+    grid_times.push(0.0);
+    grid_lambda.push(previous_lambda);
+    grid_cumulative.push(0.0);
+    for i in 1..=n_grid {
+        let t = i as f64*dt;
+        let lambda_t = lambda(t);
+        cumulative += 0.5*(previous_lambda + lambda_t)*dt;
+        grid_times.push(t);
+        grid_lambda.push(lambda_t);
+        grid_cumulative.push(cumulative);
+        previous_lambda = lambda_t;
+    }
+
+    let mut result = Vec::<Event>::new();
+    let mut s = 0.0;
+    loop {
+        s += -random::<f64>().ln();
+        if s > cumulative {
+            break;
+        }
+
+        // Bisect the cumulative grid for s, then linearly interpolate
+        // both the event time and the intensity at that time.
+        let idx = match grid_cumulative.binary_search_by(|v| v.partial_cmp(&s).unwrap()) {
+            Ok(i) => i,
+            Err(i) => i.min(n_grid),
+        };
+        let (lo, hi) = if idx == 0 { (0, 1) } else { (idx - 1, idx.min(n_grid)) };
+        let frac = if grid_cumulative[hi] > grid_cumulative[lo] {
+            (s - grid_cumulative[lo])/(grid_cumulative[hi] - grid_cumulative[lo])
+        } else { 0.0 };
+        let t = grid_times[lo] + frac*(grid_times[hi] - grid_times[lo]);
+        let lambda_t = grid_lambda[lo] + frac*(grid_lambda[hi] - grid_lambda[lo]);
+
+        result.push(Event::new(t, lambda_t));
+    }
+
+    result
+}
+
 /// Simulate a Hawkes process with an exponential kernel
 /// by utilising the linear time-complexity algorithm in [Dassios and Zhao's 2013 paper](http://eprints.lse.ac.uk/51370/1/Dassios_exact_simulation_hawkes.pdf).
 /// Returns the intensity process.
@@ -107,6 +164,184 @@ where T: Iterator<Item = f64>
 }
 
 
+/// Simulate a multivariate, mutually-exciting Hawkes process over `D` components via
+/// Ogata's thinning, with a per-(target, source) exponential kernel `alpha[i][j]`/
+/// `beta[i][j]`: λᵢ(t) = μᵢ + Σⱼ Σ_{tₖ in component j, tₖ<t} alphaᵢⱼ·exp(-betaᵢⱼ(t-tₖ)).
+/// Returns `(component, timestamp)` pairs in chronological order.
+pub fn hawkes_multivariate(tmax: f64, baselines: &[f64], alpha: &[Vec<f64>], beta: &[Vec<f64>]) -> Vec<(usize, f64)> {
+    let d = baselines.len();
+    assert!(alpha.len() == d && beta.len() == d);
+    assert!(alpha.iter().all(|row| row.len() == d) && beta.iter().all(|row| row.len() == d));
+
+    let intensities = |contrib: &Vec<Vec<f64>>| -> Vec<f64> {
+        (0..d).map(|i| baselines[i] + contrib[i].iter().sum::<f64>()).collect()
+    };
+
+    let mut t = 0.0;
+    // contrib[i][j]: decaying excitation contributed to component i by past
+    // events in component j.
+    let mut contrib = vec![vec![0.0; d]; d];
+    let mut result = Vec::<(usize, f64)>::new();
+
+    while t < tmax {
+        let lambda = intensities(&contrib);
+        let lambda_bar: f64 = lambda.iter().sum();
+        if lambda_bar <= 0.0 {
+            break;
+        }
+
+        let dt = -1.0/lambda_bar*random::<f64>().ln();
+        let candidate_t = t + dt;
+        if candidate_t > tmax {
+            break;
+        }
+
+        // Decay every source/target excitation pair to the candidate time.
+        for i in 0..d {
+            for j in 0..d {
+                contrib[i][j] *= (-beta[i][j]*dt).exp();
+            }
+        }
+        let decayed_lambda = intensities(&contrib);
+        let decayed_bar: f64 = decayed_lambda.iter().sum();
+
+        t = candidate_t;
+        if random::<f64>() < decayed_bar/lambda_bar {
+            // Accept the candidate, assigning it to component i with
+            // probability proportional to that component's intensity.
+            let u = random::<f64>()*decayed_bar;
+            let mut cumulative = 0.0;
+            let mut component = d - 1;
+            for i in 0..d {
+                cumulative += decayed_lambda[i];
+                if u < cumulative {
+                    component = i;
+                    break;
+                }
+            }
+            result.push((component, t));
+            for i in 0..d {
+                contrib[i][component] += alpha[i][component];
+            }
+        }
+    }
+
+    result
+}
+
+/// Simulate a Cox (doubly-stochastic) Poisson process: the intensity follows an
+/// Euler-Maruyama-simulated Ornstein-Uhlenbeck path (clamped at zero), and events are
+/// thinned against its running maximum. Returns the events alongside the full
+/// `(time, intensity)` trajectory of the latent driver.
+pub fn cox_process(tmax: f64, theta: f64, mu: f64, lambda0: f64, sigma: f64, dt: f64) -> (Vec<Event>, Vec<(f64, f64)>) {
+    assert!(dt > 0.0);
+    assert!(lambda0 >= 0.0);
+
+    let mut rng = thread_rng();
+    let normal = rand::distributions::Normal::new(0.0, 1.0);
+
+    let n_steps = (tmax/dt).ceil() as usize;
+    let mut path = Vec::with_capacity(n_steps + 1);
+    path.push((0.0, lambda0));
+
+    let mut lambda = lambda0;
+    for n in 1..=n_steps {
+        let t = (n as f64*dt).min(tmax);
+        let z: f64 = normal.sample(&mut rng);
+        lambda = (lambda + theta*(mu - lambda)*dt + sigma*dt.sqrt()*z).max(0.0);
+        path.push((t, lambda));
+    }
+
+    // Running maximum of the simulated path is used as the thinning bound.
+    let lambda_bar = path.iter().map(|&(_, l)| l).fold(0.0, f64::max);
+
+    let mut result = Vec::<Event>::new();
+    if lambda_bar > 0.0 {
+        let mut t = 0.0;
+        loop {
+            t += -1.0/lambda_bar*random::<f64>().ln();
+            if t > tmax {
+                break;
+            }
+            let lambda_t = interpolate_path(&path, t);
+            if random::<f64>()*lambda_bar < lambda_t {
+                result.push(Event::new(t, lambda_t));
+            }
+        }
+    }
+
+    (result, path)
+}
+
+/// Linearly interpolate a `(time, value)` path, assumed sorted by time, at
+/// an arbitrary query time. Clamps to the first/last value outside the
+/// path's range.
+fn interpolate_path(path: &[(f64, f64)], t: f64) -> f64 {
+    match path.binary_search_by(|&(pt, _)| pt.partial_cmp(&t).unwrap()) {
+        Ok(i) => path[i].1,
+        Err(i) => {
+            if i == 0 {
+                path[0].1
+            } else if i >= path.len() {
+                path[path.len() - 1].1
+            } else {
+                let (t0, l0) = path[i - 1];
+                let (t1, l1) = path[i];
+                let frac = (t - t0)/(t1 - t0);
+                l0 + frac*(l1 - l0)
+            }
+        }
+    }
+}
+
+/// Simulate a renewal process whose inter-arrival gaps are drawn from an arbitrary
+/// `sampler` (e.g. gamma, Weibull, log-normal), generalising the Poisson process's
+/// memoryless exponential gaps. The intensity field is set to the reciprocal of each
+/// event's own gap, as a rough instantaneous-rate summary.
+pub fn renewal_process<F>(tmax: f64, mut sampler: F) -> Vec<Event>
+where F: FnMut() -> f64
+{
+    let mut t = 0.0;
+    let mut result = Vec::<Event>::new();
+
+    while t < tmax {
+        let gap = sampler();
+        assert!(gap >= 0.0);
+        t += gap;
+        if t > tmax {
+            break;
+        }
+        result.push(Event::new(t, 1.0/gap));
+    }
+
+    result
+}
+
+/// Simulate a renewal process from an age-dependent hazard `h(age)`: advancing in steps
+/// of `dt`, firing with probability `h(age)*dt` and resetting the age on firing. Models
+/// refractory/bursty streams that a memoryless Poisson process cannot capture.
+pub fn hazard_renewal_process<H>(tmax: f64, hazard: H, dt: f64) -> Vec<Event>
+where H: Fn(f64) -> f64
+{
+    assert!(dt > 0.0);
+    let mut t = 0.0;
+    let mut age = 0.0;
+    let mut result = Vec::<Event>::new();
+
+    while t < tmax {
+        let h = hazard(age);
+        if random::<f64>() < h*dt {
+            result.push(Event::new(t, h));
+            age = 0.0;
+        } else {
+            age += dt;
+        }
+        t += dt;
+    }
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;